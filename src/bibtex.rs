@@ -0,0 +1,227 @@
+// Import / export BibTeX du catalogue.
+//
+// L'export produit une entrée `@book{...}` par livre, avec une clé de
+// citation stable dérivée de l'auteur et de l'année. L'import accepte les
+// entrées `@book` et `@misc`, dans n'importe quel ordre de champs, avec ou
+// sans accolades/guillemets autour des valeurs.
+use std::collections::HashMap;
+
+use crate::Livre;
+
+/// Dérive une clé de citation stable à partir de l'auteur et de l'année,
+/// utilisée à l'export (ex: "Tolkien1954").
+fn generer_cle(auteur: &str, annee: u32) -> String {
+    let nom = auteur
+        .split_whitespace()
+        .last()
+        .unwrap_or("inconnu")
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>();
+    format!("{}{}", nom, annee)
+}
+
+/// Sérialise le catalogue en un document BibTeX, une entrée `@book` par livre.
+pub fn exporter_bibtex(livres: &[Livre]) -> String {
+    let mut sortie = String::new();
+    for livre in livres {
+        let cle = generer_cle(&livre.auteur, livre.annee_publication);
+        sortie.push_str(&format!(
+            "@book{{{},\n  title = {{{}}},\n  author = {{{}}},\n  isbn = {{{}}},\n  year = {{{}}}\n}}\n\n",
+            cle, livre.titre, livre.auteur, livre.isbn, livre.annee_publication
+        ));
+    }
+    sortie
+}
+
+/// Trouve la position de l'accolade fermante correspondant à l'accolade
+/// ouvrante déjà consommée (profondeur 1 au départ).
+fn position_fermeture(s: &str) -> Option<usize> {
+    let mut profondeur = 1;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => profondeur += 1,
+            '}' => {
+                profondeur -= 1;
+                if profondeur == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Découpe le corps d'une entrée sur les virgules de premier niveau
+/// (en ignorant celles à l'intérieur d'accolades ou de guillemets).
+fn splitter_champs_top_niveau(s: &str) -> Vec<&str> {
+    let mut parties = Vec::new();
+    let mut profondeur = 0;
+    let mut dans_guillemets = false;
+    let mut debut = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => profondeur += 1,
+            '}' => profondeur -= 1,
+            // Un guillemet à l'intérieur d'accolades fait partie de la
+            // valeur (ex: un titre contenant un " "), il ne doit pas être
+            // suivi en dehors de la profondeur 0 sous peine de désynchroniser
+            // `dans_guillemets` et de faire déborder un champ sur les suivants.
+            '"' if profondeur == 0 => dans_guillemets = !dans_guillemets,
+            ',' if profondeur == 0 && !dans_guillemets => {
+                parties.push(&s[debut..i]);
+                debut = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if debut < s.len() {
+        parties.push(&s[debut..]);
+    }
+    parties
+}
+
+/// Retire les accolades ou guillemets entourant une valeur de champ.
+fn nettoyer_valeur(s: &str) -> String {
+    let s = s.trim();
+    let s = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(s);
+    let s = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s);
+    s.trim().to_string()
+}
+
+/// Parse le corps d'une entrée (après la clé) en une table champ -> valeur.
+fn parser_champs(corps: &str) -> HashMap<String, String> {
+    let mut champs = HashMap::new();
+    for partie in splitter_champs_top_niveau(corps) {
+        if let Some(pos) = partie.find('=') {
+            let nom = partie[..pos].trim().to_lowercase();
+            let valeur = nettoyer_valeur(&partie[pos + 1..]);
+            champs.insert(nom, valeur);
+        }
+    }
+    champs
+}
+
+/// Parse un document BibTeX et renvoie les livres reconnus dans les
+/// entrées `@book`/`@misc`. Les entrées d'un autre type sont ignorées.
+pub fn importer_bibtex(contenu: &str) -> Vec<Livre> {
+    let mut livres = Vec::new();
+    let mut reste = contenu;
+
+    while let Some(pos_arobase) = reste.find('@') {
+        reste = &reste[pos_arobase + 1..];
+
+        let Some(pos_ouverture) = reste.find('{') else {
+            break;
+        };
+        let type_entree = reste[..pos_ouverture].trim().to_lowercase();
+        let apres_ouverture = &reste[pos_ouverture + 1..];
+
+        let Some(pos_fermeture) = position_fermeture(apres_ouverture) else {
+            break;
+        };
+        let corps = &apres_ouverture[..pos_fermeture];
+        reste = &apres_ouverture[pos_fermeture + 1..];
+
+        if type_entree != "book" && type_entree != "misc" {
+            continue;
+        }
+
+        // Le premier champ (avant la première virgule de premier niveau) est
+        // la clé de citation ; on ne la conserve pas, `Livre` n'en a pas besoin.
+        let debut_champs = corps.find(',').map(|i| i + 1).unwrap_or(corps.len());
+        let champs = parser_champs(&corps[debut_champs..]);
+
+        let titre = champs.get("title").cloned().unwrap_or_default();
+        if titre.is_empty() {
+            continue;
+        }
+        let auteur = champs.get("author").cloned().unwrap_or_default();
+        let isbn = champs.get("isbn").cloned().unwrap_or_default();
+        let annee = champs
+            .get("year")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        livres.push(Livre {
+            titre,
+            auteur,
+            isbn,
+            annee_publication: annee,
+        });
+    }
+
+    livres
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_puis_import_redonne_le_meme_livre() {
+        let livres = vec![Livre {
+            titre: "Le Seigneur des Anneaux".to_string(),
+            auteur: "Tolkien".to_string(),
+            isbn: "9780261102385".to_string(),
+            annee_publication: 1954,
+        }];
+
+        let document = exporter_bibtex(&livres);
+        let relu = importer_bibtex(&document);
+
+        assert_eq!(relu.len(), 1);
+        assert_eq!(relu[0].titre, livres[0].titre);
+        assert_eq!(relu[0].auteur, livres[0].auteur);
+        assert_eq!(relu[0].isbn, livres[0].isbn);
+        assert_eq!(relu[0].annee_publication, livres[0].annee_publication);
+    }
+
+    #[test]
+    fn import_tolere_l_ordre_des_champs_et_les_guillemets() {
+        let document = r#"@misc{tolkien54,
+            year = "1954",
+            title = {Le Hobbit},
+            author = "Tolkien"
+        }"#;
+
+        let livres = importer_bibtex(document);
+
+        assert_eq!(livres.len(), 1);
+        assert_eq!(livres[0].titre, "Le Hobbit");
+        assert_eq!(livres[0].auteur, "Tolkien");
+        assert_eq!(livres[0].annee_publication, 1954);
+    }
+
+    #[test]
+    fn import_ignore_les_entrees_d_un_autre_type() {
+        let document = "@article{x, title = {Un Article}, author = {Quelqu'un}}";
+        assert!(importer_bibtex(document).is_empty());
+    }
+
+    #[test]
+    fn import_ignore_les_entrees_sans_titre() {
+        let document = "@book{k, author = {Tolkien}, year = {1954}}";
+        assert!(importer_bibtex(document).is_empty());
+    }
+
+    #[test]
+    fn import_ne_se_desynchronise_pas_sur_un_guillemet_dans_une_valeur_accoladee() {
+        let document = r#"@book{k, title = {The "Great" Book}, author = {Smith}, isbn = {0306406152}, year = {2001}}"#;
+
+        let livres = importer_bibtex(document);
+
+        assert_eq!(livres.len(), 1);
+        assert_eq!(livres[0].titre, "The \"Great\" Book");
+        assert_eq!(livres[0].auteur, "Smith");
+        assert_eq!(livres[0].isbn, "0306406152");
+        assert_eq!(livres[0].annee_publication, 2001);
+    }
+}
@@ -0,0 +1,176 @@
+// Détection des doublons et quasi-doublons du catalogue.
+//
+// Les doublons exacts sont repérés par ISBN normalisé. Les quasi-doublons
+// sont repérés par distance de Levenshtein normalisée sur titre+auteur. Les
+// paires détectées sont ensuite regroupées par transitivité (union-find) :
+// si A~B et B~C, les trois livres se retrouvent dans le même groupe.
+use std::collections::HashMap;
+
+use crate::isbn;
+use crate::Livre;
+
+/// Seuil de similarité par défaut pour les quasi-doublons (0.0 à 1.0).
+pub const SEUIL_SIMILARITE_DEFAUT: f64 = 0.85;
+
+/// Distance de Levenshtein classique entre deux chaînes.
+pub fn distance_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for (i, ligne) in dp.iter_mut().enumerate().take(n + 1) {
+        ligne[0] = i;
+    }
+    for (j, case) in dp[0].iter_mut().enumerate() {
+        *case = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            let cout = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cout);
+        }
+    }
+
+    dp[n][m]
+}
+
+/// Similarité normalisée (1.0 = identique, 0.0 = totalement différent) sur
+/// le titre et l'auteur concaténés en minuscules.
+fn similarite_titre_auteur(a: &Livre, b: &Livre) -> f64 {
+    let sa = format!("{}{}", a.titre, a.auteur).to_lowercase();
+    let sb = format!("{}{}", b.titre, b.auteur).to_lowercase();
+
+    let longueur_max = sa.chars().count().max(sb.chars().count());
+    if longueur_max == 0 {
+        return 1.0;
+    }
+
+    let distance = distance_levenshtein(&sa, &sb);
+    1.0 - (distance as f64) / (longueur_max as f64)
+}
+
+fn sont_doublons(a: &Livre, b: &Livre, seuil_similarite: f64) -> bool {
+    let isbn_a = isbn::vers_forme_canonique(&a.isbn);
+    let isbn_b = isbn::vers_forme_canonique(&b.isbn);
+    if !isbn_a.is_empty() && isbn_a == isbn_b {
+        return true;
+    }
+    similarite_titre_auteur(a, b) >= seuil_similarite
+}
+
+fn trouver(parent: &mut [usize], i: usize) -> usize {
+    if parent[i] != i {
+        parent[i] = trouver(parent, parent[i]);
+    }
+    parent[i]
+}
+
+fn unir(parent: &mut [usize], a: usize, b: usize) {
+    let racine_a = trouver(parent, a);
+    let racine_b = trouver(parent, b);
+    if racine_a != racine_b {
+        parent[racine_a] = racine_b;
+    }
+}
+
+/// Regroupe les index de `livres` qui sont doublons ou quasi-doublons les
+/// uns des autres (transitivement), selon `seuil_similarite`.
+pub fn detecter_doublons(livres: &[Livre], seuil_similarite: f64) -> Vec<Vec<usize>> {
+    let n = livres.len();
+    let mut parent: Vec<usize> = (0..n).collect();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if sont_doublons(&livres[i], &livres[j], seuil_similarite) {
+                unir(&mut parent, i, j);
+            }
+        }
+    }
+
+    let mut groupes: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..n {
+        let racine = trouver(&mut parent, i);
+        groupes.entry(racine).or_default().push(i);
+    }
+
+    groupes.into_values().filter(|g| g.len() > 1).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn livre(titre: &str, auteur: &str, isbn: &str) -> Livre {
+        Livre {
+            titre: titre.to_string(),
+            auteur: auteur.to_string(),
+            isbn: isbn.to_string(),
+            annee_publication: 0,
+        }
+    }
+
+    #[test]
+    fn distance_levenshtein_mots_identiques() {
+        assert_eq!(distance_levenshtein("hobbit", "hobbit"), 0);
+    }
+
+    #[test]
+    fn distance_levenshtein_substitution_unique() {
+        assert_eq!(distance_levenshtein("hobbit", "hobbet"), 1);
+    }
+
+    #[test]
+    fn similarite_titre_auteur_identique_vaut_un() {
+        let a = livre("Le Hobbit", "Tolkien", "");
+        let b = livre("Le Hobbit", "Tolkien", "");
+        assert_eq!(similarite_titre_auteur(&a, &b), 1.0);
+    }
+
+    #[test]
+    fn similarite_titre_auteur_baisse_avec_les_differences() {
+        let a = livre("Le Hobbit", "Tolkien", "");
+        let b = livre("Les Misérables", "Hugo", "");
+        assert!(similarite_titre_auteur(&a, &b) < 0.5);
+    }
+
+    #[test]
+    fn detecter_doublons_regroupe_par_isbn_normalise() {
+        let livres = vec![
+            livre("Le Hobbit", "Tolkien", "0-306-40615-2"),
+            livre("Le Hobbit (réédition)", "Tolkien", "0306406152"),
+        ];
+        let groupes = detecter_doublons(&livres, 0.85);
+        assert_eq!(groupes, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn detecter_doublons_regroupe_par_transitivite() {
+        // A~B (un seul caractère diffère) et B~C idem, mais A et C diffèrent
+        // sur deux caractères et tombent sous le seuil si on les compare
+        // directement : le groupement doit quand même les réunir tous les
+        // trois par transitivité (A~B et B~C).
+        let a = livre("dragonrider", "", "");
+        let b = livre("fragonrider", "", "");
+        let c = livre("fragonridez", "", "");
+        assert!(similarite_titre_auteur(&a, &b) >= 0.85);
+        assert!(similarite_titre_auteur(&b, &c) >= 0.85);
+        assert!(similarite_titre_auteur(&a, &c) < 0.85);
+
+        let groupes = detecter_doublons(&[a, b, c], 0.85);
+        assert_eq!(groupes.len(), 1);
+        assert_eq!(groupes[0].len(), 3);
+    }
+
+    #[test]
+    fn detecter_doublons_ignore_les_livres_distincts() {
+        let livres = vec![
+            livre("Le Hobbit", "Tolkien", "9780261102217"),
+            livre("Les Misérables", "Hugo", "9782253096344"),
+        ];
+        assert!(detecter_doublons(&livres, 0.85).is_empty());
+    }
+}
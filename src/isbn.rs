@@ -0,0 +1,155 @@
+// Validation et normalisation des ISBN.
+//
+// Un ISBN saisi par l'utilisateur peut contenir des espaces ou des tirets,
+// et être en format ISBN-10 ou ISBN-13. On normalise (espaces/tirets
+// retirés, majuscules) puis on valide la clé de contrôle ; les ISBN-10
+// valides sont convertis en ISBN-13 pour que le catalogue n'utilise qu'une
+// seule forme canonique, ce qui rend `rechercher_par_isbn` fiable.
+
+/// Retire espaces et tirets d'un ISBN.
+pub fn normaliser(isbn: &str) -> String {
+    isbn.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect()
+}
+
+fn valider_isbn10(isbn: &str) -> bool {
+    let caracteres: Vec<char> = isbn.chars().collect();
+    if caracteres.len() != 10 {
+        return false;
+    }
+
+    let mut somme = 0u32;
+    for (i, c) in caracteres.iter().enumerate() {
+        let valeur = if i == 9 && *c == 'X' {
+            10
+        } else if let Some(chiffre) = c.to_digit(10) {
+            chiffre
+        } else {
+            return false;
+        };
+        somme += valeur * (10 - i as u32);
+    }
+    somme.is_multiple_of(11)
+}
+
+fn valider_isbn13(isbn: &str) -> bool {
+    let caracteres: Vec<char> = isbn.chars().collect();
+    if caracteres.len() != 13 {
+        return false;
+    }
+
+    let chiffres: Option<Vec<u32>> = caracteres.iter().map(|c| c.to_digit(10)).collect();
+    let Some(chiffres) = chiffres else {
+        return false;
+    };
+
+    let somme: u32 = chiffres
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 0 { d } else { d * 3 })
+        .sum();
+    somme.is_multiple_of(10)
+}
+
+/// Convertit un ISBN-10 (déjà validé) en ISBN-13 : préfixe `978` et nouvelle
+/// clé de contrôle.
+fn isbn10_vers_isbn13(isbn10: &str) -> String {
+    let base = format!("978{}", &isbn10[..9]);
+    let chiffres: Vec<u32> = base.chars().map(|c| c.to_digit(10).unwrap()).collect();
+    let somme: u32 = chiffres
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 0 { d } else { d * 3 })
+        .sum();
+    let cle = (10 - somme % 10) % 10;
+    format!("{}{}", base, cle)
+}
+
+/// Valide un ISBN saisi par l'utilisateur et renvoie sa forme canonique
+/// (ISBN-13, espaces/tirets retirés). Les ISBN-10 valides sont convertis
+/// en ISBN-13. Un ISBN vide est accepté tel quel (livre sans ISBN connu,
+/// par exemple importé d'un gestionnaire bibliographique). Renvoie un
+/// message d'erreur si l'ISBN est non vide et n'est ni un ISBN-10 ni un
+/// ISBN-13 valide.
+pub fn valider_et_normaliser(isbn: &str) -> Result<String, String> {
+    let normalise = normaliser(isbn).to_uppercase();
+
+    if normalise.is_empty() {
+        return Ok(normalise);
+    }
+    if valider_isbn13(&normalise) {
+        return Ok(normalise);
+    }
+    if valider_isbn10(&normalise) {
+        return Ok(isbn10_vers_isbn13(&normalise));
+    }
+
+    Err(format!("ISBN invalide: {}", isbn))
+}
+
+/// Normalise un ISBN pour comparaison : forme ISBN-13 canonique quand
+/// l'ISBN est valide, sinon la forme simplement nettoyée (espaces/tirets
+/// retirés, majuscules) pour rester tolérant aux entrées invalides ou
+/// antérieures à la validation (ex: catalogues importés).
+pub fn vers_forme_canonique(isbn: &str) -> String {
+    valider_et_normaliser(isbn).unwrap_or_else(|_| normaliser(isbn).to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isbn10_valide_avec_chiffre_de_controle() {
+        assert!(valider_isbn10("0306406152"));
+    }
+
+    #[test]
+    fn isbn10_valide_avec_x_en_cle() {
+        assert!(valider_isbn10("080442957X"));
+    }
+
+    #[test]
+    fn isbn10_invalide_cle_incorrecte() {
+        assert!(!valider_isbn10("0306406150"));
+    }
+
+    #[test]
+    fn isbn13_valide() {
+        assert!(valider_isbn13("9780306406157"));
+    }
+
+    #[test]
+    fn isbn13_invalide_cle_incorrecte() {
+        assert!(!valider_isbn13("9780306406158"));
+    }
+
+    #[test]
+    fn conversion_isbn10_vers_isbn13() {
+        assert_eq!(isbn10_vers_isbn13("0306406152"), "9780306406157");
+    }
+
+    #[test]
+    fn valider_et_normaliser_tolere_espaces_et_tirets() {
+        assert_eq!(
+            valider_et_normaliser("0-306-40615-2").unwrap(),
+            "9780306406157"
+        );
+    }
+
+    #[test]
+    fn valider_et_normaliser_rejette_isbn_invalide() {
+        assert!(valider_et_normaliser("1234567890").is_err());
+    }
+
+    #[test]
+    fn valider_et_normaliser_accepte_isbn_vide() {
+        assert_eq!(valider_et_normaliser("").unwrap(), "");
+    }
+
+    #[test]
+    fn vers_forme_canonique_convertit_isbn10() {
+        assert_eq!(vers_forme_canonique("0306406152"), "9780306406157");
+    }
+}
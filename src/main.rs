@@ -1,8 +1,15 @@
 use eframe::egui;
 use serde::{Serialize, Deserialize};
 use std::error::Error;
-use std::fs;
-use std::path::Path;
+
+mod bibtex;
+mod doublons;
+mod isbn;
+mod recherche;
+mod stockage;
+
+use recherche::IndexRecherche;
+use stockage::{StockageJson, StockageSqlite, StorageBackend, TypeStockage};
 
 // Structure de base pour un livre
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,75 +23,119 @@ struct Livre {
 // Gestion de la bibliothèque et de la persistance
 struct Bibliotheque {
     livres: Vec<Livre>,
-    fichier: String,
+    backend: Box<dyn StorageBackend>,
+    index: IndexRecherche,
 }
 
 impl Bibliotheque {
-    // Crée une nouvelle bibliothèque ou charge une existante
-    fn new(fichier: &str) -> Self {
+    // Crée une nouvelle bibliothèque ou charge une existante, avec le
+    // support de stockage choisi
+    fn new(support: TypeStockage, fichier: &str) -> Self {
+        let backend: Box<dyn StorageBackend> = match support {
+            TypeStockage::Json => Box::new(StockageJson::new(fichier)),
+            TypeStockage::Sqlite => match StockageSqlite::new(fichier) {
+                Ok(stockage) => Box::new(stockage),
+                Err(e) => {
+                    println!("Impossible d'ouvrir la base SQLite ({}), retour au JSON.", e);
+                    Box::new(StockageJson::new(fichier))
+                }
+            },
+        };
+
         let mut bibliotheque = Bibliotheque {
             livres: Vec::new(),
-            fichier: fichier.to_string(),
+            backend,
+            index: IndexRecherche::construire(&[]),
         };
-        bibliotheque.charger_donnees().unwrap_or_else(|_| {
+        bibliotheque.livres = bibliotheque.backend.charger_tous().unwrap_or_else(|_| {
             println!("Nouvelle bibliothèque créée.");
+            Vec::new()
         });
+        bibliotheque.reconstruire_index();
         bibliotheque
     }
 
-    // Charge les livres depuis le fichier JSON
-    fn charger_donnees(&mut self) -> Result<(), Box<dyn Error>> {
-        if !Path::new(&self.fichier).exists() {
-            return Ok(());
-        }
-
-        let contenu = fs::read_to_string(&self.fichier)?;
-        if contenu.is_empty() {
-            return Ok(());
-        }
-
-        self.livres = serde_json::from_str(&contenu)?;
-        Ok(())
+    // Reconstruit l'index de recherche plein texte à partir du catalogue courant
+    fn reconstruire_index(&mut self) {
+        self.index = IndexRecherche::construire(&self.livres);
     }
 
-    // Sauvegarde les livres dans le fichier JSON
-    fn sauvegarder_donnees(&self) -> Result<(), Box<dyn Error>> {
-        let contenu = serde_json::to_string_pretty(&self.livres)?;
-        fs::write(&self.fichier, contenu)?;
-        Ok(())
-    }
+    // Valide l'ISBN, le normalise en ISBN-13, puis ajoute le livre et le persiste
+    fn ajouter_livre(&mut self, mut livre: Livre) -> Result<(), Box<dyn Error>> {
+        livre.isbn = isbn::valider_et_normaliser(&livre.isbn)?;
+
+        let mut livres_a_jour = self.livres.clone();
+        livres_a_jour.push(livre.clone());
+        self.backend.inserer(&livre, &livres_a_jour)?;
 
-    // Ajoute un livre et sauvegarde
-    fn ajouter_livre(&mut self, livre: Livre) -> Result<(), Box<dyn Error>> {
         self.livres.push(livre);
-        self.sauvegarder_donnees()?;
+        self.reconstruire_index();
         Ok(())
     }
 
-    // Recherche par titre (insensible à la casse)
+    // Recherche plein texte sur le titre et l'auteur, classée par pertinence TF-IDF
     fn rechercher_par_titre(&self, titre: &str) -> Vec<&Livre> {
-        self.livres
-            .iter()
-            .filter(|livre| livre.titre.to_lowercase().contains(&titre.to_lowercase()))
-            .collect()
+        self.index.rechercher(titre, &self.livres)
     }
 
-    // Recherche par ISBN (recherche exacte)
+    // Recherche par ISBN (recherche exacte, sur la forme canonique ISBN-13),
+    // servie depuis le catalogue en mémoire plutôt que par le backend : voir
+    // la doc de `StorageBackend` pour pourquoi (l'index TF-IDF et la
+    // détection de doublons gardent de toute façon tout le catalogue en RAM)
     fn rechercher_par_isbn(&self, isbn: &str) -> Option<&Livre> {
-        self.livres
-            .iter()
-            .find(|livre| livre.isbn.to_lowercase() == isbn.to_lowercase())
+        let normalise = isbn::vers_forme_canonique(isbn);
+        self.livres.iter().find(|livre| livre.isbn == normalise)
     }
 
-    // Supprime un livre par son index
+    // Supprime un livre par son index et persiste la suppression
     fn retirer_livre(&mut self, index: usize) -> Result<(), Box<dyn Error>> {
         if index >= self.livres.len() {
             return Err("Index invalide".into());
         }
+        let livre = self.livres[index].clone();
+        let mut livres_a_jour = self.livres.clone();
+        livres_a_jour.remove(index);
+        self.backend.supprimer(&livre, &livres_a_jour)?;
         self.livres.remove(index);
-        self.sauvegarder_donnees()?;
+        self.reconstruire_index();
         Ok(())
     }
+
+    // Exporte le catalogue entier au format BibTeX
+    fn exporter_bibtex(&self) -> String {
+        bibtex::exporter_bibtex(&self.livres)
+    }
+
+    // Importe des livres depuis un document BibTeX, en ignorant ceux dont
+    // l'ISBN est invalide (un ISBN absent est accepté) ou déjà présent dans
+    // le catalogue (comparaison sur la forme canonique ISBN-13). Renvoie le
+    // nombre de livres ajoutés et le nombre ignorés ; une erreur de stockage
+    // interrompt l'import.
+    fn importer_bibtex(&mut self, contenu: &str) -> Result<(usize, usize), Box<dyn Error>> {
+        let mut ajoutes = 0;
+        let mut ignores = 0;
+        for livre in bibtex::importer_bibtex(contenu) {
+            if isbn::valider_et_normaliser(&livre.isbn).is_err() {
+                ignores += 1;
+                continue;
+            }
+
+            let isbn_normalise = isbn::vers_forme_canonique(&livre.isbn);
+            let deja_present = !isbn_normalise.is_empty()
+                && self
+                    .livres
+                    .iter()
+                    .any(|l| isbn::vers_forme_canonique(&l.isbn) == isbn_normalise);
+            if deja_present {
+                ignores += 1;
+                continue;
+            }
+
+            self.ajouter_livre(livre)?;
+            ajoutes += 1;
+        }
+        Ok((ajoutes, ignores))
+    }
 }
 
 // Application principale
@@ -93,6 +144,9 @@ struct BibliothequeApp {
     nouveau_livre: Livre,
     recherche_titre: String,
     recherche_isbn: String,
+    seuil_doublons: f64,
+    bibtex_import: String,
+    bibtex_export: String,
     onglet_actif: Onglet,
     message: String,
     message_type: MessageType,
@@ -103,6 +157,7 @@ enum Onglet {
     Liste,
     Ajout,
     Recherche,
+    Doublons,
 }
 
 #[derive(PartialEq)]
@@ -112,10 +167,12 @@ enum MessageType {
     Succes,
 }
 
-impl Default for BibliothequeApp {
-    fn default() -> Self {
+impl BibliothequeApp {
+    // Construit l'application avec le support de stockage choisi (voir
+    // `--sqlite` sur la ligne de commande)
+    fn new(support: TypeStockage, fichier: &str) -> Self {
         Self {
-            bibliotheque: Bibliotheque::new("bibliotheque.json"),
+            bibliotheque: Bibliotheque::new(support, fichier),
             nouveau_livre: Livre {
                 titre: String::new(),
                 auteur: String::new(),
@@ -124,6 +181,9 @@ impl Default for BibliothequeApp {
             },
             recherche_titre: String::new(),
             recherche_isbn: String::new(),
+            seuil_doublons: doublons::SEUIL_SIMILARITE_DEFAUT,
+            bibtex_import: String::new(),
+            bibtex_export: String::new(),
             onglet_actif: Onglet::Liste,
             message: String::new(),
             message_type: MessageType::Info,
@@ -131,6 +191,12 @@ impl Default for BibliothequeApp {
     }
 }
 
+impl Default for BibliothequeApp {
+    fn default() -> Self {
+        Self::new(TypeStockage::Json, "bibliotheque.json")
+    }
+}
+
 impl eframe::App for BibliothequeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
@@ -145,6 +211,9 @@ impl eframe::App for BibliothequeApp {
                 if ui.button("Rechercher").clicked() {
                     self.onglet_actif = Onglet::Recherche;
                 }
+                if ui.button("Doublons").clicked() {
+                    self.onglet_actif = Onglet::Doublons;
+                }
             });
         });
 
@@ -184,6 +253,44 @@ impl eframe::App for BibliothequeApp {
                             }
                         }
                     }
+
+                    ui.add_space(10.0);
+                    ui.collapsing("Import / Export BibTeX", |ui| {
+                        if ui.button("Exporter vers BibTeX").clicked() {
+                            self.bibtex_export = self.bibliotheque.exporter_bibtex();
+                        }
+                        if !self.bibtex_export.is_empty() {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut self.bibtex_export)
+                                    .desired_rows(6)
+                                    .code_editor(),
+                            );
+                        }
+
+                        ui.add_space(10.0);
+                        ui.label("Coller un document BibTeX à importer :");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.bibtex_import)
+                                .desired_rows(6)
+                                .code_editor(),
+                        );
+                        if ui.button("Importer depuis BibTeX").clicked() {
+                            match self.bibliotheque.importer_bibtex(&self.bibtex_import) {
+                                Ok((ajoutes, ignores)) => {
+                                    self.message = format!(
+                                        "{} livre(s) importé(s), {} ignoré(s).",
+                                        ajoutes, ignores
+                                    );
+                                    self.message_type = MessageType::Succes;
+                                    self.bibtex_import.clear();
+                                }
+                                Err(e) => {
+                                    self.message = format!("Erreur: {}", e);
+                                    self.message_type = MessageType::Erreur;
+                                }
+                            }
+                        }
+                    });
                 }
                 Onglet::Ajout => {
                     ui.heading("Ajouter un Nouveau Livre");
@@ -275,6 +382,55 @@ impl eframe::App for BibliothequeApp {
                         }
                     }
                 }
+                Onglet::Doublons => {
+                    ui.heading("Doublons et Quasi-Doublons");
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Seuil de similarité: ");
+                        ui.add(egui::Slider::new(&mut self.seuil_doublons, 0.5..=1.0));
+                    });
+                    ui.add_space(10.0);
+
+                    let groupes = doublons::detecter_doublons(&self.bibliotheque.livres, self.seuil_doublons);
+
+                    if groupes.is_empty() {
+                        ui.label("Aucun doublon détecté.");
+                    } else {
+                        let mut index_a_supprimer = None;
+
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            for (numero, groupe) in groupes.iter().enumerate() {
+                                ui.label(format!("Groupe {}", numero + 1));
+                                for &index in groupe {
+                                    let livre = &self.bibliotheque.livres[index];
+                                    ui.horizontal(|ui| {
+                                        ui.vertical(|ui| {
+                                            ui.heading(&livre.titre);
+                                            ui.label(format!("Auteur: {}", livre.auteur));
+                                            ui.label(format!("ISBN: {}", livre.isbn));
+                                            ui.label(format!("Année: {}", livre.annee_publication));
+                                        });
+                                        if ui.button("Supprimer").clicked() {
+                                            index_a_supprimer = Some(index);
+                                        }
+                                    });
+                                }
+                                ui.separator();
+                            }
+                        });
+
+                        if let Some(index) = index_a_supprimer {
+                            if let Err(e) = self.bibliotheque.retirer_livre(index) {
+                                self.message = format!("Erreur: {}", e);
+                                self.message_type = MessageType::Erreur;
+                            } else {
+                                self.message = "Livre supprimé avec succès.".to_string();
+                                self.message_type = MessageType::Succes;
+                            }
+                        }
+                    }
+                }
             }
             
             if !self.message.is_empty() {
@@ -295,10 +451,18 @@ fn main() -> Result<(), eframe::Error> {
             .with_inner_size([800.0, 600.0]),
         ..Default::default()
     };
-    
+
+    // `--sqlite` bascule la persistance sur SQLite (fichier bibliotheque.db)
+    // au lieu du fichier JSON par défaut ; utile pour les grands catalogues.
+    let (support, fichier) = if std::env::args().any(|arg| arg == "--sqlite") {
+        (TypeStockage::Sqlite, "bibliotheque.db")
+    } else {
+        (TypeStockage::Json, "bibliotheque.json")
+    };
+
     eframe::run_native(
         "Gestion de Bibliothèque",
         options,
-        Box::new(|_cc| Box::new(BibliothequeApp::default())),
+        Box::new(move |_cc| Box::new(BibliothequeApp::new(support, fichier))),
     )
 }
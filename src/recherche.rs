@@ -0,0 +1,130 @@
+// Index de recherche plein texte sur le catalogue.
+//
+// Un index inversé associe chaque terme (titre + auteur, normalisé) à la
+// liste des livres qui le contiennent, avec sa fréquence documentaire.
+// Les résultats sont classés par pertinence TF-IDF plutôt que par simple
+// correspondance de sous-chaîne.
+use std::collections::HashMap;
+
+use crate::Livre;
+
+/// Découpe un texte en termes normalisés (minuscules, ponctuation ignorée).
+fn tokeniser(texte: &str) -> Vec<String> {
+    texte
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|terme| !terme.is_empty())
+        .map(|terme| terme.to_string())
+        .collect()
+}
+
+/// Index inversé titre+auteur avec classement TF-IDF.
+pub struct IndexRecherche {
+    // terme -> liste des (index du livre, nombre d'occurrences dans ce livre)
+    index: HashMap<String, Vec<(usize, usize)>>,
+    nombre_documents: usize,
+}
+
+impl IndexRecherche {
+    /// Construit l'index à partir du catalogue courant.
+    pub fn construire(livres: &[Livre]) -> Self {
+        let mut index: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+
+        for (i, livre) in livres.iter().enumerate() {
+            let mut occurrences: HashMap<String, usize> = HashMap::new();
+            for terme in tokeniser(&livre.titre).into_iter().chain(tokeniser(&livre.auteur)) {
+                *occurrences.entry(terme).or_insert(0) += 1;
+            }
+            for (terme, compte) in occurrences {
+                index.entry(terme).or_default().push((i, compte));
+            }
+        }
+
+        IndexRecherche {
+            index,
+            nombre_documents: livres.len(),
+        }
+    }
+
+    /// Recherche `requete` dans l'index et renvoie les livres triés par
+    /// pertinence décroissante (score TF-IDF cumulé sur les termes).
+    pub fn rechercher<'a>(&self, requete: &str, livres: &'a [Livre]) -> Vec<&'a Livre> {
+        let termes = tokeniser(requete);
+        if termes.is_empty() || self.nombre_documents == 0 {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for terme in &termes {
+            if let Some(occurrences) = self.index.get(terme) {
+                let df = occurrences.len();
+                let idf = ((self.nombre_documents as f64) / (df as f64)).ln();
+                for &(indice_livre, tf) in occurrences {
+                    *scores.entry(indice_livre).or_insert(0.0) += (tf as f64) * idf;
+                }
+            }
+        }
+
+        let mut resultats: Vec<(usize, f64)> = scores.into_iter().collect();
+        resultats.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        resultats
+            .into_iter()
+            .filter_map(|(indice, _)| livres.get(indice))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn livre(titre: &str, auteur: &str) -> Livre {
+        Livre {
+            titre: titre.to_string(),
+            auteur: auteur.to_string(),
+            isbn: String::new(),
+            annee_publication: 0,
+        }
+    }
+
+    #[test]
+    fn classe_par_score_cumule_sur_plusieurs_termes() {
+        let livres = vec![
+            livre("Le Seigneur des Anneaux", "Tolkien"),
+            livre("Bilbo le Hobbit", "Tolkien"),
+            livre("Les Misérables", "Hugo"),
+        ];
+        let index = IndexRecherche::construire(&livres);
+
+        let resultats = index.rechercher("Tolkien Anneaux", &livres);
+
+        assert_eq!(resultats.len(), 2);
+        assert_eq!(resultats[0].titre, "Le Seigneur des Anneaux");
+    }
+
+    #[test]
+    fn terme_rare_l_emporte_sur_terme_frequent() {
+        let livres = vec![
+            livre("Tolkien raconte la Terre du Milieu", "Tolkien"),
+            livre("Tolkien et ses lettres", "Tolkien"),
+            livre("Tolkien invente les Hobbits", "Tolkien"),
+        ];
+        let index = IndexRecherche::construire(&livres);
+
+        // "tolkien" apparaît dans les trois livres (df=3) alors que
+        // "hobbits" n'apparaît que dans le troisième (df=1), qui doit donc
+        // être classé en tête malgré le terme commun partagé par tous.
+        let resultats = index.rechercher("Tolkien Hobbits", &livres);
+
+        assert_eq!(resultats[0].titre, "Tolkien invente les Hobbits");
+    }
+
+    #[test]
+    fn requete_vide_ne_renvoie_aucun_resultat() {
+        let livres = vec![livre("Un Titre", "Un Auteur")];
+        let index = IndexRecherche::construire(&livres);
+
+        assert!(index.rechercher("", &livres).is_empty());
+    }
+}
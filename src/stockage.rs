@@ -0,0 +1,143 @@
+// Couche de persistance de la bibliothèque.
+//
+// `Bibliotheque` délègue la lecture/écriture à un `StorageBackend`, ce qui
+// permet de choisir entre un simple fichier JSON et une base SQLite sans
+// changer le reste du code.
+use rusqlite::{params, Connection};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::Livre;
+
+/// Support de stockage choisi à la création de la `Bibliotheque`.
+pub enum TypeStockage {
+    Json,
+    Sqlite,
+}
+
+/// Abstraction commune aux différents supports de persistance.
+///
+/// `Bibliotheque` garde tout le catalogue en mémoire (c'est elle qui sert
+/// les recherches) ; le backend n'a qu'à persister les mutations. `inserer`
+/// et `supprimer` reçoivent aussi le catalogue à jour pour que le backend
+/// JSON, qui ne sait écrire qu'un fichier entier, n'ait pas à le relire
+/// depuis le disque à chaque opération.
+///
+/// Le backend n'expose donc pas de méthode de recherche : l'index TF-IDF
+/// (`recherche::IndexRecherche`) et la détection de doublons (`doublons`)
+/// ont de toute façon besoin du catalogue entier résident en mémoire, donc
+/// déléguer `WHERE isbn = ?` / `WHERE titre LIKE ?` à SQLite ne ferait pas
+/// l'économie de charger tout le catalogue ; ça ajouterait juste un second
+/// chemin de lecture à maintenir en plus de l'index. Le gain du backend
+/// SQLite reste réel pour les écritures (insert/delete en une ligne plutôt
+/// qu'une réécriture du fichier entier).
+pub trait StorageBackend {
+    fn charger_tous(&self) -> Result<Vec<Livre>, Box<dyn Error>>;
+    fn inserer(&mut self, livre: &Livre, livres_a_jour: &[Livre]) -> Result<(), Box<dyn Error>>;
+    fn supprimer(&mut self, livre: &Livre, livres_a_jour: &[Livre]) -> Result<(), Box<dyn Error>>;
+}
+
+/// Backend historique : tout le catalogue tient dans un fichier JSON.
+pub struct StockageJson {
+    fichier: String,
+}
+
+impl StockageJson {
+    pub fn new(fichier: &str) -> Self {
+        StockageJson {
+            fichier: fichier.to_string(),
+        }
+    }
+
+    fn ecrire_tout(&self, livres: &[Livre]) -> Result<(), Box<dyn Error>> {
+        let contenu = serde_json::to_string_pretty(livres)?;
+        fs::write(&self.fichier, contenu)?;
+        Ok(())
+    }
+}
+
+impl StorageBackend for StockageJson {
+    fn charger_tous(&self) -> Result<Vec<Livre>, Box<dyn Error>> {
+        if !Path::new(&self.fichier).exists() {
+            return Ok(Vec::new());
+        }
+        let contenu = fs::read_to_string(&self.fichier)?;
+        if contenu.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_str(&contenu)?)
+    }
+
+    fn inserer(&mut self, _livre: &Livre, livres_a_jour: &[Livre]) -> Result<(), Box<dyn Error>> {
+        self.ecrire_tout(livres_a_jour)
+    }
+
+    fn supprimer(&mut self, _livre: &Livre, livres_a_jour: &[Livre]) -> Result<(), Box<dyn Error>> {
+        self.ecrire_tout(livres_a_jour)
+    }
+}
+
+/// Backend SQLite : chaque ajout/suppression ne touche qu'une ligne. Les
+/// recherches ne passent pas par SQLite : elles sont servies par le
+/// catalogue chargé en mémoire par `Bibliotheque`.
+pub struct StockageSqlite {
+    connexion: Connection,
+}
+
+impl StockageSqlite {
+    pub fn new(fichier: &str) -> Result<Self, Box<dyn Error>> {
+        let connexion = Connection::open(fichier)?;
+        connexion.execute(
+            "CREATE TABLE IF NOT EXISTS livres (
+                titre TEXT NOT NULL,
+                auteur TEXT NOT NULL,
+                isbn TEXT NOT NULL,
+                annee_publication INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(StockageSqlite { connexion })
+    }
+
+    fn ligne_vers_livre(row: &rusqlite::Row) -> rusqlite::Result<Livre> {
+        Ok(Livre {
+            titre: row.get(0)?,
+            auteur: row.get(1)?,
+            isbn: row.get(2)?,
+            annee_publication: row.get(3)?,
+        })
+    }
+}
+
+impl StorageBackend for StockageSqlite {
+    fn charger_tous(&self) -> Result<Vec<Livre>, Box<dyn Error>> {
+        let mut requete = self
+            .connexion
+            .prepare("SELECT titre, auteur, isbn, annee_publication FROM livres")?;
+        let livres = requete
+            .query_map([], Self::ligne_vers_livre)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(livres)
+    }
+
+    fn inserer(&mut self, livre: &Livre, _livres_a_jour: &[Livre]) -> Result<(), Box<dyn Error>> {
+        self.connexion.execute(
+            "INSERT INTO livres (titre, auteur, isbn, annee_publication) VALUES (?1, ?2, ?3, ?4)",
+            params![livre.titre, livre.auteur, livre.isbn, livre.annee_publication],
+        )?;
+        Ok(())
+    }
+
+    fn supprimer(&mut self, livre: &Livre, _livres_a_jour: &[Livre]) -> Result<(), Box<dyn Error>> {
+        self.connexion.execute(
+            "DELETE FROM livres WHERE rowid = (
+                SELECT rowid FROM livres
+                WHERE titre = ?1 AND auteur = ?2 AND isbn = ?3 AND annee_publication = ?4
+                LIMIT 1
+            )",
+            params![livre.titre, livre.auteur, livre.isbn, livre.annee_publication],
+        )?;
+        Ok(())
+    }
+}